@@ -0,0 +1,112 @@
+//! Minimal `std::io`-compatible types, used in place of `std::io` when the
+//! `std` feature is disabled (requires the `core_io` feature and `alloc`).
+//!
+//! Only the subset of `std::io` that `CharBuffer` actually relies on is
+//! reproduced here, so the crate compiles unchanged against either backend.
+
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// Minimal stand-in for `std::io::Read`.
+///
+/// Unlike `std::io::Read`, `buf` is handed over uninitialized: implementors
+/// write bytes through `MaybeUninit::write` and report how many leading
+/// bytes of `buf` they initialized. This lets callers pass in uninitialized
+/// chunk storage directly instead of first zeroing it, without ever
+/// materializing a `&mut [u8]` over memory that isn't actually initialized.
+pub trait Read {
+    /// Pulls some bytes from this source into `buf`, returning how many
+    /// leading bytes of `buf` were initialized.
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize>;
+
+    /// Creates an adapter that will read at most `limit` bytes from `self`.
+    /// Mirrors `std::io::Read::take`.
+    fn take(self, limit: u64) -> Take<Self>
+    where
+        Self: Sized
+    {
+        Take { inner: self, limit }
+    }
+}
+
+impl<R: Read + ?Sized> Read for &mut R {
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        (**self).read(buf)
+    }
+}
+
+impl Read for &[u8] {
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        let amount = core::cmp::min(buf.len(), self.len());
+        let (head, tail) = self.split_at(amount);
+
+        for (slot, &byte) in buf[..amount].iter_mut().zip(head) {
+            slot.write(byte);
+        }
+
+        *self = tail;
+
+        Result::Ok(amount)
+    }
+}
+
+/// Adapter that limits the number of bytes read from the wrapped reader.
+/// Returned by [`Read::take`].
+pub struct Take<R> {
+    inner: R,
+    limit: u64,
+}
+
+impl<R: Read> Read for Take<R> {
+    fn read(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<usize> {
+        let max = core::cmp::min(buf.len() as u64, self.limit) as usize;
+        let read = self.inner.read(&mut buf[..max])?;
+        self.limit -= read as u64;
+
+        Result::Ok(read)
+    }
+}
+
+/// Categories of I/O error, mirroring the subset of `std::io::ErrorKind`
+/// this crate relies on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// An entity was not found.
+    NotFound,
+    /// Permission was denied for the requested operation.
+    PermissionDenied,
+    /// Input data was invalid.
+    InvalidInput,
+    /// Parsed input data was syntactically invalid for its target type.
+    InvalidData,
+    /// The end of the stream was reached before the requested amount of data could be read.
+    UnexpectedEof,
+}
+
+/// Minimal stand-in for `std::io::Error`.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: &'static str,
+}
+
+impl Error {
+    /// Creates a new error from an `ErrorKind` and a static message.
+    pub fn new(kind: ErrorKind, message: &'static str) -> Error {
+        Error { kind, message }
+    }
+
+    /// Returns the corresponding `ErrorKind` for this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.message)
+    }
+}
+
+/// Minimal stand-in for `std::io::Result`.
+pub type Result<T> = core::result::Result<T, Error>;
@@ -1,11 +1,22 @@
 #![warn(clippy::all, rust_2018_idioms)]
 #![deny(missing_docs, unused, nonstandard_style, future_incompatible)]
-#![feature(test)]
+#![cfg_attr(all(test, feature = "std"), feature(test))]
+#![cfg_attr(feature = "std", feature(read_buf, core_io_borrowed_buf))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! # Compiler (System-oriented Programming)
 //!
 //! A simple compiler reimplementation inspired by a former study project.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(not(feature = "std"), not(feature = "core_io")))]
+compile_error!("Either the `std` or the `core_io` feature must be enabled.");
+
+#[cfg(all(not(feature = "std"), feature = "core_io"))]
+mod io;
+
 mod buffer;
 
 pub use crate::buffer::CharBuffer;